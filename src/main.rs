@@ -1,17 +1,33 @@
+use base64::Engine as _;
+use blake2::Blake2b512;
 use clap::Parser;
 use colored::Colorize;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
 use indicatif::{ProgressBar, ProgressStyle};
 use miette::{Context, IntoDiagnostic, Result};
 use serde::Deserialize;
+use sha2::{Digest, Sha256};
 use std::fs;
 use std::io::{Read, Write};
+use std::path::Path;
 use std::process::Command;
 use std::time::Duration;
+use subtle::ConstantTimeEq;
+
+/// Hex-encoded ed25519 public key used to verify release signatures unless
+/// `--pubkey` overrides it. Release builds must inject the project's real
+/// signing key by setting `MQ_UPDATE_TRUSTED_PUBKEY` at compile time (e.g. via
+/// the release CI's environment); a build without it can still verify via
+/// `--pubkey`, but `--target`/default updates will refuse to proceed unless
+/// `--no-verify` is passed, since there's no key to check against.
+const TRUSTED_PUBLIC_KEY_HEX: Option<&str> = option_env!("MQ_UPDATE_TRUSTED_PUBKEY");
 
 #[derive(Deserialize)]
 struct Release {
     tag_name: String,
     assets: Vec<Asset>,
+    #[serde(default)]
+    prerelease: bool,
 }
 
 #[derive(Deserialize)]
@@ -20,23 +36,99 @@ struct Asset {
     browser_download_url: String,
 }
 
+/// A parsed `--target` value, modeled on how version managers like nenv
+/// resolve a requested version against the releases available upstream.
+enum VersionSpec {
+    /// Resolve to the newest release on the selected channel.
+    Latest,
+    /// Resolve to exactly this version.
+    Exact(semver::Version),
+    /// Resolve to the highest version satisfying this requirement.
+    Req(semver::VersionReq),
+}
+
+impl VersionSpec {
+    fn parse(input: &str) -> Result<Self> {
+        if input.eq_ignore_ascii_case("latest") {
+            return Ok(VersionSpec::Latest);
+        }
+
+        let unprefixed = input.trim_start_matches('v');
+        if let Ok(version) = semver::Version::parse(unprefixed) {
+            return Ok(VersionSpec::Exact(version));
+        }
+
+        semver::VersionReq::parse(input)
+            .map(VersionSpec::Req)
+            .into_diagnostic()
+            .wrap_err(format!("Invalid version or version requirement: {}", input))
+    }
+}
+
+#[derive(clap::ValueEnum, Clone, Debug, PartialEq, Eq)]
+enum Channel {
+    Stable,
+    Prerelease,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum CliCommand {
+    /// List cached versions or roll back to a previously installed one
+    Rollback {
+        /// Subcommand name whose cache to inspect (e.g. "check" for mq-check)
+        subcommand: Option<String>,
+
+        /// Version to roll back to (defaults to the most recent cached version
+        /// before the currently installed one)
+        #[arg(long = "to")]
+        to: Option<String>,
+
+        /// List cached versions instead of rolling back
+        #[arg(long)]
+        list: bool,
+    },
+
+    /// Update mq-update itself to the latest (or a targeted) version
+    SelfUpdate,
+}
+
 #[derive(Parser, Debug)]
 #[command(author, version, about = "Update mq to the latest version", long_about = None)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<CliCommand>,
+
     /// Subcommand name to install/update (e.g., "check" for mq-check)
     subcommand: Option<String>,
 
-    /// Target version to install (defaults to latest)
+    /// Target version to install: "latest", an exact version (e.g. "0.5.12"),
+    /// or a semver requirement (e.g. "^0.5", ">=0.4, <0.6")
     #[arg(short = 't', long = "target")]
     target_version: Option<String>,
 
+    /// Release channel to consider when resolving "latest" or a version requirement
+    #[arg(long, value_enum, default_value = "stable")]
+    channel: Channel,
+
     /// Force reinstall even if already up-to-date
     #[arg(short, long)]
     force: bool,
 
+    /// Update every installed mq-* subcommand found on PATH instead of a single target
+    #[arg(long)]
+    all: bool,
+
     /// Show current version
     #[arg(long)]
     current: bool,
+
+    /// Skip SHA-256 checksum and signature verification (not recommended)
+    #[arg(long = "no-verify")]
+    no_verify: bool,
+
+    /// Path to an ed25519 public key to verify against, instead of the embedded one
+    #[arg(long = "pubkey")]
+    pubkey: Option<std::path::PathBuf>,
 }
 
 fn get_binary_path(binary_name: &str) -> Result<Option<std::path::PathBuf>> {
@@ -82,25 +174,42 @@ fn get_binary_version(binary_name: &str) -> Result<Option<String>> {
     Ok(Some(version))
 }
 
-fn get_latest_release(repo: &str, target_version: Option<&String>) -> Result<Release> {
-    let url = if let Some(version) = target_version {
-        let tag = if version.starts_with('v') {
-            version.clone()
-        } else {
-            format!("v{}", version)
-        };
-        format!(
-            "https://api.github.com/repos/{}/releases/tags/{}",
-            repo, tag
-        )
-    } else {
-        format!("https://api.github.com/repos/{}/releases/latest", repo)
-    };
-
-    let client = reqwest::blocking::Client::builder()
+fn github_client() -> Result<reqwest::blocking::Client> {
+    reqwest::blocking::Client::builder()
         .user_agent("mq-update")
         .build()
-        .into_diagnostic()?;
+        .into_diagnostic()
+}
+
+fn fetch_release_by_tag(repo: &str, tag: &str) -> Result<Release> {
+    let client = github_client()?;
+    let url = format!(
+        "https://api.github.com/repos/{}/releases/tags/{}",
+        repo, tag
+    );
+
+    let response = client
+        .get(&url)
+        .send()
+        .into_diagnostic()
+        .wrap_err("Failed to fetch release information from GitHub")?;
+
+    if !response.status().is_success() {
+        return Err(miette::miette!(
+            "Failed to fetch release: HTTP {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json::<Release>()
+        .into_diagnostic()
+        .wrap_err("Failed to parse release information")
+}
+
+fn fetch_newest_release(repo: &str) -> Result<Release> {
+    let client = github_client()?;
+    let url = format!("https://api.github.com/repos/{}/releases/latest", repo);
 
     let response = client
         .get(&url)
@@ -121,6 +230,100 @@ fn get_latest_release(repo: &str, target_version: Option<&String>) -> Result<Rel
         .wrap_err("Failed to parse release information")
 }
 
+/// Parses the `rel="next"` URL out of a GitHub `Link` response header.
+fn parse_next_link(link_header: &str) -> Option<String> {
+    link_header.split(',').find_map(|part| {
+        let part = part.trim();
+        let (url_part, rel_part) = part.split_once(';')?;
+        if rel_part.trim() != "rel=\"next\"" {
+            return None;
+        }
+        Some(
+            url_part
+                .trim()
+                .trim_start_matches('<')
+                .trim_end_matches('>')
+                .to_string(),
+        )
+    })
+}
+
+/// Pages through every release for `repo`, following the `Link: rel="next"` header.
+fn list_all_releases(repo: &str) -> Result<Vec<Release>> {
+    let client = github_client()?;
+    let mut url = format!(
+        "https://api.github.com/repos/{}/releases?per_page=100",
+        repo
+    );
+    let mut releases = Vec::new();
+
+    loop {
+        let response = client
+            .get(&url)
+            .send()
+            .into_diagnostic()
+            .wrap_err("Failed to fetch release list from GitHub")?;
+
+        if !response.status().is_success() {
+            return Err(miette::miette!(
+                "Failed to fetch release list: HTTP {}",
+                response.status()
+            ));
+        }
+
+        let next_url = response
+            .headers()
+            .get("link")
+            .and_then(|h| h.to_str().ok())
+            .and_then(parse_next_link);
+
+        let page: Vec<Release> = response
+            .json()
+            .into_diagnostic()
+            .wrap_err("Failed to parse release list")?;
+        releases.extend(page);
+
+        match next_url {
+            Some(next) => url = next,
+            None => break,
+        }
+    }
+
+    Ok(releases)
+}
+
+fn matches_channel(release: &Release, channel: &Channel) -> bool {
+    match channel {
+        Channel::Stable => !release.prerelease,
+        Channel::Prerelease => true,
+    }
+}
+
+fn resolve_release(repo: &str, spec: &VersionSpec, channel: &Channel) -> Result<Release> {
+    match spec {
+        VersionSpec::Exact(version) => fetch_release_by_tag(repo, &format!("v{}", version)),
+        VersionSpec::Latest if *channel == Channel::Stable => fetch_newest_release(repo),
+        VersionSpec::Latest => list_all_releases(repo)?
+            .into_iter()
+            .find(|release| matches_channel(release, channel))
+            .ok_or_else(|| miette::miette!("No releases found for {}", repo)),
+        VersionSpec::Req(req) => list_all_releases(repo)?
+            .into_iter()
+            .filter(|release| matches_channel(release, channel))
+            .filter_map(|release| {
+                semver::Version::parse(release.tag_name.trim_start_matches('v'))
+                    .ok()
+                    .map(|version| (version, release))
+            })
+            .filter(|(version, _)| req.matches(version))
+            .max_by(|(a, _), (b, _)| a.cmp(b))
+            .map(|(_, release)| release)
+            .ok_or_else(|| {
+                miette::miette!("No release of {} satisfies requirement {}", repo, req)
+            }),
+    }
+}
+
 fn get_target_arch() -> &'static str {
     #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
     return "aarch64-apple-darwin";
@@ -184,155 +387,246 @@ fn print_logo() {
     println!();
 }
 
-fn download_and_replace(
-    download_url: &str,
-    mq_path: &std::path::Path,
-    force: bool,
-    is_new_install: bool,
-) -> Result<()> {
-    if !force && !is_new_install {
-        println!();
-        println!(
-            "{}",
-            "  ╭────────────────────────────────────────╮".bright_cyan()
-        );
-        println!(
-            "{}",
-            "  │                                        │".bright_cyan()
-        );
-        println!(
-            "  │  {}    │",
-            "⚠  The binary will be replaced    ".bright_yellow().bold()
-        );
-        println!(
-            "{}",
-            "  │                                        │".bright_cyan()
-        );
-        println!(
-            "{}",
-            "  ╰────────────────────────────────────────╯".bright_cyan()
-        );
-        print!(
-            "\n  {} {} ",
-            "❯".bright_cyan().bold(),
-            "Do you want to continue? [Y/n]".bold()
-        );
-        std::io::stdout().flush().into_diagnostic()?;
-
-        let mut input = String::new();
-        std::io::stdin().read_line(&mut input).into_diagnostic()?;
+/// Where to find the signature for a release asset, and which format it's in.
+/// `.minisig` assets are real minisign (https://jedisct1.github.io/minisign/)
+/// signatures and are parsed/verified as such in `verify_minisign`; `.sig` is
+/// this project's own fallback, a raw 64-byte ed25519 signature.
+enum SignatureAsset {
+    Minisign(String),
+    Raw(String),
+}
 
-        if !input.trim().is_empty() && !input.trim().eq_ignore_ascii_case("y") {
-            println!();
-            println!(
-                "  {} {}",
-                "✗".bright_red().bold(),
-                "Update cancelled".bright_red()
-            );
-            println!();
-            return Err(miette::miette!("Update cancelled by user"));
+impl SignatureAsset {
+    fn url(&self) -> &str {
+        match self {
+            SignatureAsset::Minisign(url) | SignatureAsset::Raw(url) => url,
         }
     }
+}
 
-    println!();
-    println!(
-        "{}",
-        "  ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan()
-    );
-    println!("  📦 {}", "Downloading binary...".bright_white().bold());
-    println!(
-        "{}",
-        "  ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan()
-    );
-    println!();
+/// URLs of the sibling verification assets for a given release asset, if published.
+struct VerificationAssets {
+    sha256_url: Option<String>,
+    signature: Option<SignatureAsset>,
+}
 
-    let client = reqwest::blocking::Client::builder()
-        .user_agent("mq-update")
-        .build()
-        .into_diagnostic()?;
+fn load_public_key(pubkey_path: Option<&Path>) -> Result<VerifyingKey> {
+    let hex_key = if let Some(path) = pubkey_path {
+        fs::read_to_string(path)
+            .into_diagnostic()
+            .wrap_err("Failed to read public key file")?
+    } else {
+        TRUSTED_PUBLIC_KEY_HEX.map(str::to_string).ok_or_else(|| {
+            miette::miette!(
+                "No trusted public key is embedded in this build (set MQ_UPDATE_TRUSTED_PUBKEY \
+                 at compile time); pass --pubkey <path> to verify against a different key, or \
+                 --no-verify to skip authenticity verification"
+            )
+        })?
+    };
 
-    let mut response = client
-        .get(download_url)
-        .send()
+    let key_bytes = hex::decode(hex_key.trim())
         .into_diagnostic()
-        .wrap_err("Failed to download binary")?;
+        .wrap_err("Failed to decode public key as hex")?;
+    let key_bytes: [u8; 32] = key_bytes
+        .try_into()
+        .map_err(|_| miette::miette!("Public key must be 32 bytes"))?;
 
-    if !response.status().is_success() {
-        return Err(miette::miette!(
-            "Failed to download binary: HTTP {}",
-            response.status()
-        ));
+    VerifyingKey::from_bytes(&key_bytes)
+        .into_diagnostic()
+        .wrap_err("Invalid ed25519 public key")
+}
+
+fn verify_checksum(buffer: &[u8], expected: &str) -> Result<()> {
+    let expected = expected
+        .split_whitespace()
+        .next()
+        .unwrap_or(expected)
+        .to_lowercase();
+
+    let mut hasher = Sha256::new();
+    hasher.update(buffer);
+    let actual = hex::encode(hasher.finalize());
+
+    if actual.as_bytes().ct_eq(expected.as_bytes()).into() {
+        Ok(())
+    } else {
+        Err(miette::miette!(
+            "SHA-256 checksum mismatch: expected {}, got {}",
+            expected,
+            actual
+        ))
     }
+}
 
-    let total_size = response.content_length().unwrap_or(0);
+fn verify_signature(buffer: &[u8], signature_bytes: &[u8], public_key: &VerifyingKey) -> Result<()> {
+    let signature_bytes: [u8; 64] = signature_bytes
+        .try_into()
+        .map_err(|_| miette::miette!("Signature must be 64 bytes"))?;
+    let signature = Signature::from_bytes(&signature_bytes);
 
-    let pb = ProgressBar::new(total_size);
-    pb.set_style(
-        ProgressStyle::default_bar()
-            .template("  {spinner:.bright_cyan} {msg} [{bar:40.bright_cyan/blue}] {bytes}/{total_bytes} {elapsed_precise}")
-            .into_diagnostic()?
-            .progress_chars("━╸─")
-    );
-    pb.set_message("Downloading".to_string());
+    public_key
+        .verify(buffer, &signature)
+        .into_diagnostic()
+        .wrap_err("Signature verification failed")
+}
 
-    let mut buffer = Vec::new();
-    let mut downloaded: u64 = 0;
+/// Parses and verifies a minisign (https://jedisct1.github.io/minisign/)
+/// signature file against `public_key`. The wire format is four lines:
+///
+///   untrusted comment: <text>
+///   base64(<algorithm (2 bytes)><key id (8 bytes)><signature (64 bytes)>)
+///   trusted comment: <text>
+///   base64(<global signature (64 bytes)>)
+///
+/// `algorithm` is `Ed` for a signature over the raw file, or `ED` for a
+/// signature over its BLAKE2b-512 hash (minisign's prehashed mode, used for
+/// large files). The global signature covers the 64-byte file signature
+/// followed by the trusted comment, so it's checked too -- otherwise a
+/// tampered comment would be accepted as long as the file signature itself
+/// still checked out. Unlike minisign's own key files, `--pubkey`/the
+/// embedded key is just the hex-encoded 32-byte ed25519 key with no key id, so the key-id match minisign normally
+/// does is skipped; the signature checks below still have to pass.
+fn verify_minisign(buffer: &[u8], minisig_text: &str, public_key: &VerifyingKey) -> Result<()> {
+    let mut lines = minisig_text.lines();
+
+    lines
+        .next()
+        .filter(|line| line.starts_with("untrusted comment:"))
+        .ok_or_else(|| miette::miette!("Malformed .minisig file: missing untrusted comment line"))?;
+
+    let sig_blob = base64::engine::general_purpose::STANDARD
+        .decode(
+            lines
+                .next()
+                .ok_or_else(|| miette::miette!("Malformed .minisig file: missing signature line"))?
+                .trim(),
+        )
+        .into_diagnostic()
+        .wrap_err("Failed to base64-decode minisign signature line")?;
+    let sig_blob: [u8; 74] = sig_blob.try_into().map_err(|_| {
+        miette::miette!("Malformed .minisig file: signature block must be 74 bytes")
+    })?;
+
+    let trusted_comment = lines
+        .next()
+        .and_then(|line| line.strip_prefix("trusted comment:"))
+        .map(str::trim_start)
+        .ok_or_else(|| miette::miette!("Malformed .minisig file: missing trusted comment line"))?;
+
+    let global_sig = base64::engine::general_purpose::STANDARD
+        .decode(
+            lines
+                .next()
+                .ok_or_else(|| {
+                    miette::miette!("Malformed .minisig file: missing global signature line")
+                })?
+                .trim(),
+        )
+        .into_diagnostic()
+        .wrap_err("Failed to base64-decode minisign global signature")?;
+    let global_sig: [u8; 64] = global_sig.try_into().map_err(|_| {
+        miette::miette!("Malformed .minisig file: global signature must be 64 bytes")
+    })?;
+
+    let signature = Signature::from_bytes(
+        &sig_blob[10..74]
+            .try_into()
+            .expect("sig_blob is exactly 74 bytes"),
+    );
 
-    loop {
-        let mut chunk = vec![0; 8192];
-        match response.read(&mut chunk) {
-            Ok(0) => break,
-            Ok(n) => {
-                buffer.extend_from_slice(&chunk[..n]);
-                downloaded += n as u64;
-                pb.set_position(downloaded);
-            }
-            Err(e) => return Err(miette::miette!("Download failed: {}", e)),
+    match [sig_blob[0], sig_blob[1]] {
+        [b'E', b'd'] => public_key
+            .verify(buffer, &signature)
+            .into_diagnostic()
+            .wrap_err("minisign signature verification failed"),
+        [b'E', b'D'] => {
+            let digest = {
+                use blake2::Digest as _;
+                let mut hasher = Blake2b512::new();
+                hasher.update(buffer);
+                hasher.finalize()
+            };
+            public_key
+                .verify(&digest, &signature)
+                .into_diagnostic()
+                .wrap_err("minisign signature verification failed")
         }
-    }
+        other => Err(miette::miette!(
+            "Unsupported minisign algorithm {:?}; expected \"Ed\" or \"ED\"",
+            String::from_utf8_lossy(&other)
+        )),
+    }?;
+
+    let mut global_message = sig_blob[10..74].to_vec();
+    global_message.extend_from_slice(trusted_comment.as_bytes());
+    let global_signature = Signature::from_bytes(&global_sig);
+    public_key
+        .verify(&global_message, &global_signature)
+        .into_diagnostic()
+        .wrap_err("minisign trusted comment verification failed")
+}
 
-    pb.finish_and_clear();
+fn confirm_replacement(force: bool, is_new_install: bool) -> Result<()> {
+    if force || is_new_install {
+        return Ok(());
+    }
 
+    println!();
     println!(
-        "\n  {} {}\n",
-        "✓".bright_green().bold(),
-        "Download complete!".bright_green().bold()
+        "{}",
+        "  ╭────────────────────────────────────────╮".bright_cyan()
     );
-
-    // Create backup
-    let spinner = ProgressBar::new_spinner();
-    spinner.set_style(
-        ProgressStyle::default_spinner()
-            .template("  {spinner:.bright_cyan} {msg}")
-            .into_diagnostic()?,
+    println!(
+        "{}",
+        "  │                                        │".bright_cyan()
     );
-    spinner.set_message("Creating backup...".to_string());
-    spinner.enable_steady_tick(Duration::from_millis(80));
+    println!(
+        "  │  {}    │",
+        "⚠  The binary will be replaced    ".bright_yellow().bold()
+    );
+    println!(
+        "{}",
+        "  │                                        │".bright_cyan()
+    );
+    println!(
+        "{}",
+        "  ╰────────────────────────────────────────╯".bright_cyan()
+    );
+    print!(
+        "\n  {} {} ",
+        "❯".bright_cyan().bold(),
+        "Do you want to continue? [Y/n]".bold()
+    );
+    std::io::stdout().flush().into_diagnostic()?;
 
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input).into_diagnostic()?;
+
+    if !input.trim().is_empty() && !input.trim().eq_ignore_ascii_case("y") {
+        println!();
+        println!(
+            "  {} {}",
+            "✗".bright_red().bold(),
+            "Update cancelled".bright_red()
+        );
+        println!();
+        return Err(miette::miette!("Update cancelled by user"));
+    }
+
+    Ok(())
+}
+
+/// Atomically replaces `mq_path` with `buffer`'s contents, keeping a `.bak`
+/// of the previous binary until the rename has succeeded.
+fn replace_binary(buffer: &[u8], mq_path: &Path) -> Result<()> {
     let backup_path = mq_path.with_extension("bak");
     if mq_path.exists() {
         fs::copy(mq_path, &backup_path)
             .into_diagnostic()
             .wrap_err("Failed to create backup")?;
-        spinner.finish_and_clear();
-        println!(
-            "  {} Backup created: {}",
-            "✓".bright_green().bold(),
-            backup_path.display().to_string().bright_black()
-        );
-    } else {
-        spinner.finish_and_clear();
     }
 
-    // Write to temporary file first to avoid corrupting the running binary
-    let spinner = ProgressBar::new_spinner();
-    spinner.set_style(
-        ProgressStyle::default_spinner()
-            .template("  {spinner:.bright_cyan} {msg}")
-            .into_diagnostic()?,
-    );
-    spinner.set_message("Replacing binary...".to_string());
-    spinner.enable_steady_tick(Duration::from_millis(80));
     let temp_path = mq_path.with_extension("tmp");
 
     // Clean up any existing temp file
@@ -340,7 +634,7 @@ fn download_and_replace(
         let _ = fs::remove_file(&temp_path);
     }
 
-    fs::write(&temp_path, &buffer)
+    fs::write(&temp_path, buffer)
         .into_diagnostic()
         .wrap_err("Failed to write new binary to temporary file")?;
 
@@ -363,78 +657,403 @@ fn download_and_replace(
         let _ = fs::remove_file(&backup_path);
     }
 
-    spinner.finish_and_clear();
-    println!(
-        "  {} {}",
-        "✓".bright_green().bold(),
-        "Binary replaced successfully!".bright_green().bold()
-    );
-
     Ok(())
 }
 
-fn main() -> Result<()> {
-    let args = Args::parse();
+/// Directory holding every cached version of `binary_name`, keyed by version.
+fn cache_dir(binary_name: &str) -> Result<std::path::PathBuf> {
+    let home = std::env::var("HOME")
+        .into_diagnostic()
+        .wrap_err("Failed to get HOME directory")?;
+    Ok(std::path::PathBuf::from(home)
+        .join(".mq")
+        .join("cache")
+        .join(binary_name))
+}
 
-    print_logo();
+/// Rejects version strings that are unsafe to use as a single path component
+/// (e.g. a release tag like `v../../../../tmp/evil` from an untrusted GitHub
+/// API response), so callers can't be tricked into writing outside the cache.
+fn sanitize_version_component(version: &str) -> Result<&str> {
+    if version.is_empty()
+        || version.contains('/')
+        || version.contains('\\')
+        || version.contains("..")
+    {
+        return Err(miette::miette!(
+            "Refusing to use unsafe version string as a cache path: {}",
+            version
+        ));
+    }
+    Ok(version)
+}
 
-    let (binary_name, repo, display_name) = if let Some(ref sub) = args.subcommand {
-        (
-            format!("mq-{}", sub),
-            format!("harehare/mq-{}", sub),
-            format!("mq-{}", sub),
-        )
-    } else {
-        (
-            "mq".to_string(),
-            "harehare/mq".to_string(),
-            "mq".to_string(),
-        )
-    };
+fn cached_binary_path(binary_name: &str, version: &str) -> Result<std::path::PathBuf> {
+    let version = sanitize_version_component(version)?;
+    Ok(cache_dir(binary_name)?.join(version).join(binary_name))
+}
 
-    let binary_path = get_binary_path(&binary_name)?;
-    let is_new_install = binary_path.is_none();
-    let current_version = if is_new_install {
-        None
-    } else {
-        get_binary_version(&binary_name)?
-    };
+/// Lists cached versions of `binary_name` with their size on disk, oldest first.
+fn list_cached_versions(binary_name: &str) -> Result<Vec<(String, u64)>> {
+    let dir = cache_dir(binary_name)?;
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
 
-    if args.current {
-        if is_new_install {
-            println!(
-                "\n  📦 {}\n  {} {}\n  {}\n",
-                format!("{} is not installed", display_name)
-                    .bright_white()
-                    .bold(),
-                "├─".bright_black(),
-                "not found".bright_yellow().bold(),
-                "└─────────────────────────────".bright_black()
-            );
-        } else if let Some(ref ver) = current_version {
+    let mut versions = Vec::new();
+    for entry in fs::read_dir(&dir).into_diagnostic()? {
+        let entry = entry.into_diagnostic()?;
+        if !entry.file_type().into_diagnostic()?.is_dir() {
+            continue;
+        }
+        let version = entry.file_name().to_string_lossy().to_string();
+        let size = fs::metadata(entry.path().join(binary_name))
+            .map(|metadata| metadata.len())
+            .unwrap_or(0);
+        versions.push((version, size));
+    }
+
+    versions.sort_by(|(a, _), (b, _)| {
+        match (semver::Version::parse(a), semver::Version::parse(b)) {
+            (Ok(a), Ok(b)) => a.cmp(&b),
+            _ => a.cmp(b),
+        }
+    });
+
+    Ok(versions)
+}
+
+/// Copies a freshly installed binary into the version cache for future reuse
+/// by the cache check in `update_one` or by `rollback`.
+fn cache_binary(binary_name: &str, version: &str, install_path: &Path) -> Result<()> {
+    let version = sanitize_version_component(version)?;
+    let dest_dir = cache_dir(binary_name)?.join(version);
+    fs::create_dir_all(&dest_dir)
+        .into_diagnostic()
+        .wrap_err("Failed to create cache directory")?;
+    fs::copy(install_path, dest_dir.join(binary_name))
+        .into_diagnostic()
+        .wrap_err("Failed to cache binary")?;
+    Ok(())
+}
+
+fn run_rollback(subcommand: Option<String>, to: Option<String>, list: bool) -> Result<()> {
+    let binary_name = match subcommand {
+        Some(sub) => format!("mq-{}", sub),
+        None => "mq".to_string(),
+    };
+    let display_name = binary_name.clone();
+
+    let versions = list_cached_versions(&binary_name)?;
+
+    if list {
+        if versions.is_empty() {
             println!(
-                "\n  📦 {}\n  {} {}\n  {}\n",
-                format!("Current {} version", display_name)
-                    .bright_white()
-                    .bold(),
-                "├─".bright_black(),
-                ver.bright_green().bold(),
-                "└─────────────────────────────".bright_black()
+                "\n  📦 {}\n",
+                format!("No cached versions of {}", display_name).bright_yellow()
             );
-        } else {
+            return Ok(());
+        }
+
+        println!(
+            "\n  📦 {}",
+            format!("Cached versions of {}", display_name)
+                .bright_white()
+                .bold()
+        );
+        for (version, size) in &versions {
             println!(
-                "\n  📦 {}\n  {} {}\n  {}\n",
-                format!("Current {} version", display_name)
-                    .bright_white()
-                    .bold(),
+                "  {} {}  {}",
                 "├─".bright_black(),
-                "unknown".bright_yellow().bold(),
-                "└─────────────────────────────".bright_black()
+                version.bright_cyan(),
+                format!("({} bytes)", size).bright_black()
             );
         }
+        println!();
+        return Ok(());
+    }
+
+    if versions.is_empty() {
+        return Err(miette::miette!(
+            "No cached versions of {} to roll back to",
+            display_name
+        ));
+    }
+
+    let install_path = get_binary_path(&binary_name)?
+        .ok_or_else(|| miette::miette!("{} is not installed", display_name))?;
+    let current_version = get_binary_version(&binary_name)?;
+
+    let target_version = match to {
+        Some(version) => version,
+        None => versions
+            .iter()
+            .rev()
+            .map(|(version, _)| version.clone())
+            .find(|version| match current_version.as_deref() {
+                Some(current) => {
+                    match (semver::Version::parse(version), semver::Version::parse(current)) {
+                        (Ok(candidate), Ok(current)) => candidate < current,
+                        _ => version != current,
+                    }
+                }
+                None => true,
+            })
+            .ok_or_else(|| {
+                miette::miette!("No older cached version of {} available", display_name)
+            })?,
+    };
+
+    println!(
+        "\n  📦 {}\n  {} {} {} {}",
+        format!("Rolling back {}", display_name)
+            .bright_white()
+            .bold(),
+        "├─".bright_black(),
+        current_version.as_deref().unwrap_or("unknown").bright_cyan(),
+        "→".bright_white(),
+        target_version.bright_green().bold()
+    );
+
+    let cached_path = cached_binary_path(&binary_name, &target_version)?;
+    if !cached_path.exists() {
+        return Err(miette::miette!(
+            "Version {} of {} is not in the cache",
+            target_version,
+            display_name
+        ));
+    }
+
+    confirm_replacement(false, false)?;
+
+    let buffer = fs::read(&cached_path)
+        .into_diagnostic()
+        .wrap_err("Failed to read cached binary")?;
+    replace_binary(&buffer, &install_path)?;
+
+    println!(
+        "\n  {} {}\n",
+        "✓".bright_green().bold(),
+        format!("Rolled back {} to {}", display_name, target_version)
+            .bright_green()
+            .bold()
+    );
+
+    Ok(())
+}
+
+/// Downloads `url` into memory with a progress bar, matching the style used
+/// throughout the update flow.
+fn download_binary(client: &reqwest::blocking::Client, url: &str) -> Result<Vec<u8>> {
+    let mut response = client
+        .get(url)
+        .send()
+        .into_diagnostic()
+        .wrap_err("Failed to download binary")?;
+
+    if !response.status().is_success() {
+        return Err(miette::miette!(
+            "Failed to download binary: HTTP {}",
+            response.status()
+        ));
+    }
+
+    let total_size = response.content_length().unwrap_or(0);
+
+    let pb = ProgressBar::new(total_size);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("  {spinner:.bright_cyan} {msg} [{bar:40.bright_cyan/blue}] {bytes}/{total_bytes} {elapsed_precise}")
+            .into_diagnostic()?
+            .progress_chars("━╸─")
+    );
+    pb.set_message("Downloading".to_string());
+
+    let mut buffer = Vec::new();
+    let mut downloaded: u64 = 0;
+
+    loop {
+        let mut chunk = vec![0; 8192];
+        match response.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(n) => {
+                buffer.extend_from_slice(&chunk[..n]);
+                downloaded += n as u64;
+                pb.set_position(downloaded);
+            }
+            Err(e) => return Err(miette::miette!("Download failed: {}", e)),
+        }
+    }
+
+    pb.finish_and_clear();
+
+    println!(
+        "\n  {} {}\n",
+        "✓".bright_green().bold(),
+        "Download complete!".bright_green().bold()
+    );
+
+    Ok(buffer)
+}
+
+/// Checks `buffer` against the published checksum/signature assets, unless `no_verify` is set.
+fn verify_release_asset(
+    client: &reqwest::blocking::Client,
+    buffer: &[u8],
+    verification: &VerificationAssets,
+    no_verify: bool,
+    pubkey_path: Option<&Path>,
+) -> Result<()> {
+    if no_verify {
+        println!(
+            "  {} {}",
+            "⚠".bright_yellow().bold(),
+            "Skipping integrity verification (--no-verify)".bright_yellow()
+        );
         return Ok(());
     }
 
+    let spinner = ProgressBar::new_spinner();
+    spinner.set_style(
+        ProgressStyle::default_spinner()
+            .template("  {spinner:.bright_cyan} {msg}")
+            .into_diagnostic()?,
+    );
+    spinner.set_message("Verifying integrity...".to_string());
+    spinner.enable_steady_tick(Duration::from_millis(80));
+
+    let sha256_url = verification.sha256_url.as_deref().ok_or_else(|| {
+        miette::miette!(
+            "No .sha256 checksum asset was published for this release; pass --no-verify to skip"
+        )
+    })?;
+    let expected_checksum = client
+        .get(sha256_url)
+        .send()
+        .into_diagnostic()
+        .wrap_err("Failed to download checksum")?
+        .text()
+        .into_diagnostic()
+        .wrap_err("Failed to read checksum")?;
+    verify_checksum(buffer, &expected_checksum)?;
+
+    let signature = verification.signature.as_ref().ok_or_else(|| {
+        miette::miette!(
+            "No signature asset (.minisig/.sig) was published for this release; a checksum \
+             alone cannot prove authenticity, since whoever publishes a malicious release also \
+             controls the accompanying .sha256 file. Pass --no-verify to skip authenticity \
+             verification"
+        )
+    })?;
+    let signature_bytes = client
+        .get(signature.url())
+        .send()
+        .into_diagnostic()
+        .wrap_err("Failed to download signature")?
+        .bytes()
+        .into_diagnostic()
+        .wrap_err("Failed to read signature")?;
+    let public_key = load_public_key(pubkey_path)?;
+    match signature {
+        SignatureAsset::Minisign(_) => {
+            let minisig_text = std::str::from_utf8(&signature_bytes)
+                .into_diagnostic()
+                .wrap_err("minisign signature file was not valid UTF-8")?;
+            verify_minisign(buffer, minisig_text, &public_key)?;
+        }
+        SignatureAsset::Raw(_) => verify_signature(buffer, &signature_bytes, &public_key)?,
+    }
+
+    spinner.finish_and_clear();
+    println!(
+        "  {} {}",
+        "✓".bright_green().bold(),
+        "Integrity verified".bright_green().bold()
+    );
+
+    Ok(())
+}
+
+fn download_and_replace(
+    download_url: &str,
+    mq_path: &std::path::Path,
+    force: bool,
+    is_new_install: bool,
+    verification: VerificationAssets,
+    no_verify: bool,
+    pubkey_path: Option<&Path>,
+) -> Result<()> {
+    confirm_replacement(force, is_new_install)?;
+
+    println!();
+    println!(
+        "{}",
+        "  ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan()
+    );
+    println!("  📦 {}", "Downloading binary...".bright_white().bold());
+    println!(
+        "{}",
+        "  ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan()
+    );
+    println!();
+
+    let client = github_client()?;
+    let buffer = download_binary(&client, download_url)?;
+
+    verify_release_asset(&client, &buffer, &verification, no_verify, pubkey_path)?;
+
+    let spinner = ProgressBar::new_spinner();
+    spinner.set_style(
+        ProgressStyle::default_spinner()
+            .template("  {spinner:.bright_cyan} {msg}")
+            .into_diagnostic()?,
+    );
+    spinner.set_message("Replacing binary...".to_string());
+    spinner.enable_steady_tick(Duration::from_millis(80));
+
+    replace_binary(&buffer, mq_path)?;
+
+    spinner.finish_and_clear();
+    println!(
+        "  {} {}",
+        "✓".bright_green().bold(),
+        "Binary replaced successfully!".bright_green().bold()
+    );
+
+    Ok(())
+}
+
+/// Outcome of updating a single `mq-*` binary, used to render the `--all` summary table.
+enum UpdateOutcome {
+    Installed(String),
+    Updated { from: String, to: String },
+    UpToDate(String),
+}
+
+/// Resolves, downloads (or reuses from cache) and installs the release selected
+/// by `--target` (the latest one by default) of a single binary. Shared by the single-target flow in `main` and by the
+/// `--all` sweep in `run_all`. `assume_yes` skips the per-binary confirmation
+/// prompt (used by `run_all`, which confirms once up front for the whole sweep)
+/// without implying `--force`, so already up-to-date binaries are still skipped.
+/// `prefetched_release` lets `run_all` reuse the release it already resolved
+/// while deciding whether to prompt at all, instead of hitting the GitHub API
+/// for the same repo a second time.
+fn update_one(
+    binary_name: &str,
+    repo: &str,
+    display_name: &str,
+    args: &Args,
+    assume_yes: bool,
+    prefetched_release: Option<Release>,
+) -> Result<UpdateOutcome> {
+    let binary_path = get_binary_path(binary_name)?;
+    let is_new_install = binary_path.is_none();
+    let current_version = if is_new_install {
+        None
+    } else {
+        get_binary_version(binary_name)?
+    };
+
     if is_new_install {
         println!(
             "  📦 {}\n  {} {}\n  {}",
@@ -468,7 +1087,16 @@ fn main() -> Result<()> {
     spinner.set_message("Checking for updates...".to_string());
     spinner.enable_steady_tick(Duration::from_millis(80));
 
-    let release = get_latest_release(&repo, args.target_version.as_ref())?;
+    let release = match prefetched_release {
+        Some(release) => release,
+        None => {
+            let version_spec = match args.target_version.as_deref() {
+                Some(target) => VersionSpec::parse(target)?,
+                None => VersionSpec::Latest,
+            };
+            resolve_release(repo, &version_spec, &args.channel)?
+        }
+    };
     let target_version = release.tag_name.trim_start_matches('v');
 
     spinner.finish_and_clear();
@@ -492,37 +1120,9 @@ fn main() -> Result<()> {
             "│".bright_black(),
             "  ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan()
         );
-        return Ok(());
+        return Ok(UpdateOutcome::UpToDate(target_version.to_string()));
     }
 
-    let target_arch = get_target_arch();
-    let asset_name = format!("{}-{}", binary_name, target_arch);
-
-    let asset = release
-        .assets
-        .iter()
-        .find(|a| a.name == asset_name)
-        .ok_or_else(|| {
-            miette::miette!(
-                "Could not find binary for architecture: {}. Available assets: {}",
-                target_arch,
-                release
-                    .assets
-                    .iter()
-                    .map(|a| &a.name)
-                    .cloned()
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            )
-        })?;
-
-    println!(
-        "\n  🔗 {}\n  {} {}",
-        "Target asset".bright_white().bold(),
-        "└─".bright_black(),
-        asset.name.bright_black()
-    );
-
     let install_path = if let Some(path) = binary_path {
         path
     } else {
@@ -534,15 +1134,86 @@ fn main() -> Result<()> {
         fs::create_dir_all(&bin_dir)
             .into_diagnostic()
             .wrap_err("Failed to create installation directory")?;
-        bin_dir.join(&binary_name)
+        bin_dir.join(binary_name)
     };
 
-    download_and_replace(
-        &asset.browser_download_url,
-        &install_path,
-        args.force,
-        is_new_install,
-    )?;
+    let cached_path = cached_binary_path(binary_name, target_version)?;
+    if !args.force && cached_path.exists() {
+        println!(
+            "\n  💾 {}",
+            format!("Using cached {} {}", display_name, target_version).bright_white()
+        );
+        confirm_replacement(args.force || assume_yes, is_new_install)?;
+        let buffer = fs::read(&cached_path)
+            .into_diagnostic()
+            .wrap_err("Failed to read cached binary")?;
+        replace_binary(&buffer, &install_path)?;
+        println!(
+            "  {} {}",
+            "✓".bright_green().bold(),
+            "Binary replaced successfully!".bright_green().bold()
+        );
+    } else {
+        let target_arch = get_target_arch();
+        let asset_name = format!("{}-{}", binary_name, target_arch);
+
+        let asset = release
+            .assets
+            .iter()
+            .find(|a| a.name == asset_name)
+            .ok_or_else(|| {
+                miette::miette!(
+                    "Could not find binary for architecture: {}. Available assets: {}",
+                    target_arch,
+                    release
+                        .assets
+                        .iter()
+                        .map(|a| &a.name)
+                        .cloned()
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })?;
+
+        println!(
+            "\n  🔗 {}\n  {} {}",
+            "Target asset".bright_white().bold(),
+            "└─".bright_black(),
+            asset.name.bright_black()
+        );
+
+        let verification = VerificationAssets {
+            sha256_url: release
+                .assets
+                .iter()
+                .find(|a| a.name == format!("{}.sha256", asset.name))
+                .map(|a| a.browser_download_url.clone()),
+            signature: release
+                .assets
+                .iter()
+                .find(|a| a.name == format!("{}.minisig", asset.name))
+                .map(|a| SignatureAsset::Minisign(a.browser_download_url.clone()))
+                .or_else(|| {
+                    release
+                        .assets
+                        .iter()
+                        .find(|a| a.name == format!("{}.sig", asset.name))
+                        .map(|a| SignatureAsset::Raw(a.browser_download_url.clone()))
+                }),
+        };
+
+        download_and_replace(
+            &asset.browser_download_url,
+            &install_path,
+            args.force || assume_yes,
+            is_new_install,
+            verification,
+            args.no_verify,
+            args.pubkey.as_deref(),
+        )?;
+
+        cache_binary(binary_name, target_version, &install_path)?;
+    }
 
     if is_new_install {
         println!(
@@ -577,12 +1248,596 @@ fn main() -> Result<()> {
                 .bright_green()
                 .bold(),
             "│".bright_black(),
-            current_version.unwrap_or_default().bright_cyan(),
+            current_version.as_deref().unwrap_or_default().bright_cyan(),
             "→".bright_white(),
             target_version.bright_green().bold(),
             "  ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan()
         );
     }
 
+    if is_new_install {
+        Ok(UpdateOutcome::Installed(target_version.to_string()))
+    } else {
+        Ok(UpdateOutcome::Updated {
+            from: current_version.unwrap_or_else(|| "unknown".to_string()),
+            to: target_version.to_string(),
+        })
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path)
+        .map(|metadata| metadata.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(windows)]
+fn is_executable(path: &Path) -> bool {
+    path.extension()
+        .map(|ext| ext.eq_ignore_ascii_case("exe"))
+        .unwrap_or(false)
+}
+
+/// Scans every directory on `PATH` for `mq-*` executables, excluding `mq-update` itself.
+fn discover_installed_subcommands() -> Vec<String> {
+    let path_var = std::env::var_os("PATH").unwrap_or_default();
+    let mut found = std::collections::BTreeSet::new();
+
+    for dir in std::env::split_paths(&path_var) {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let name = name.strip_suffix(".exe").unwrap_or(&name).to_string();
+
+            if !name.starts_with("mq-") || name == "mq-update" {
+                continue;
+            }
+
+            if is_executable(&entry.path()) {
+                found.insert(name);
+            }
+        }
+    }
+
+    found.into_iter().collect()
+}
+
+fn run_all(args: &Args) -> Result<()> {
+    let binaries = discover_installed_subcommands();
+
+    if binaries.is_empty() {
+        println!(
+            "\n  📦 {}\n",
+            "No installed mq-* subcommands were found on PATH".bright_yellow()
+        );
+        return Ok(());
+    }
+
+    println!(
+        "\n  📦 {}",
+        format!("Updating {} mq subcommand(s)", binaries.len())
+            .bright_white()
+            .bold()
+    );
+
+    let mut rows: Vec<(String, String)> = Vec::new();
+
+    // Resolve every binary's target release up front so we know whether
+    // anything actually needs replacing before asking to confirm. Without
+    // this, the common steady-state run (everything already current) would
+    // still block on a confirmation prompt for no reason.
+    let mut planned: Vec<(String, String, Release)> = Vec::new();
+    let mut any_out_of_date = args.force;
+
+    for name in &binaries {
+        let repo = format!("harehare/{}", name);
+        let version_spec = match args.target_version.as_deref() {
+            Some(target) => VersionSpec::parse(target),
+            None => Ok(VersionSpec::Latest),
+        };
+        let release =
+            version_spec.and_then(|spec| resolve_release(&repo, &spec, &args.channel));
+
+        match release {
+            Ok(release) => {
+                let target_version = release.tag_name.trim_start_matches('v');
+                if get_binary_version(name)?.as_deref() != Some(target_version) {
+                    any_out_of_date = true;
+                }
+                planned.push((name.clone(), repo, release));
+            }
+            Err(e) => {
+                println!(
+                    "  {} {}: {}",
+                    "✗".bright_red().bold(),
+                    name.bright_white(),
+                    e.to_string().bright_red()
+                );
+                rows.push((name.clone(), format!("failed: {}", e)));
+            }
+        }
+    }
+
+    // Confirm once for the whole sweep instead of once per binary, otherwise
+    // --all stalls on the first out-of-date subcommand waiting for input.
+    if any_out_of_date {
+        confirm_replacement(args.force, false)?;
+    }
+
+    for (name, repo, release) in planned {
+        println!(
+            "\n{}",
+            "  ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan()
+        );
+
+        match update_one(&name, &repo, &name, args, true, Some(release)) {
+            Ok(UpdateOutcome::Updated { from, to }) => {
+                rows.push((name.clone(), format!("{} → {}", from, to)));
+            }
+            Ok(UpdateOutcome::Installed(version)) => {
+                rows.push((name.clone(), format!("installed {}", version)));
+            }
+            Ok(UpdateOutcome::UpToDate(version)) => {
+                rows.push((name.clone(), format!("up to date ({})", version)));
+            }
+            Err(e) => {
+                println!(
+                    "  {} {}: {}",
+                    "✗".bright_red().bold(),
+                    name.bright_white(),
+                    e.to_string().bright_red()
+                );
+                rows.push((name.clone(), format!("failed: {}", e)));
+            }
+        }
+    }
+
+    println!(
+        "\n{}\n\n  {}\n",
+        "  ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan(),
+        "Summary".bright_white().bold()
+    );
+    for (name, status) in &rows {
+        println!("  {} {}: {}", "├─".bright_black(), name.bright_cyan(), status);
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Atomically replaces the currently running `mq-update` executable.
+///
+/// On Unix this is the same rename-over-a-running-binary trick used for every
+/// other install. On Windows the OS refuses to rename over a file that's
+/// mapped into a running process, so the live binary is moved aside to
+/// `mq-update.old` first and cleaned up the next time `mq-update` starts.
+fn replace_running_executable(buffer: &[u8], current_exe: &Path) -> Result<()> {
+    #[cfg(not(windows))]
+    {
+        replace_binary(buffer, current_exe)
+    }
+
+    #[cfg(windows)]
+    {
+        let old_path = current_exe.with_file_name("mq-update.old");
+        if old_path.exists() {
+            let _ = fs::remove_file(&old_path);
+        }
+
+        fs::rename(current_exe, &old_path)
+            .into_diagnostic()
+            .wrap_err("Failed to move the running mq-update binary aside")?;
+
+        fs::write(current_exe, buffer)
+            .into_diagnostic()
+            .wrap_err("Failed to write the new mq-update binary")?;
+
+        Ok(())
+    }
+}
+
+/// Deletes a `mq-update.old` left behind by a Windows self-update, if present.
+#[cfg(windows)]
+fn cleanup_self_update_leftovers() {
+    if let Ok(current_exe) = std::env::current_exe() {
+        let old_path = current_exe.with_file_name("mq-update.old");
+        if old_path.exists() {
+            let _ = fs::remove_file(&old_path);
+        }
+    }
+}
+
+#[cfg(not(windows))]
+fn cleanup_self_update_leftovers() {}
+
+fn self_update(args: &Args) -> Result<()> {
+    let repo = "harehare/mq-update";
+    let current_version = env!("CARGO_PKG_VERSION");
+    let current_exe = std::env::current_exe()
+        .into_diagnostic()
+        .wrap_err("Failed to locate the running mq-update executable")?;
+
+    println!(
+        "  📦 {}\n  {} {}\n  {}",
+        "Current mq-update version".bright_white().bold(),
+        "├─".bright_black(),
+        current_version.bright_cyan().bold(),
+        "│".bright_black()
+    );
+
+    let spinner = ProgressBar::new_spinner();
+    spinner.set_style(
+        ProgressStyle::default_spinner()
+            .template("  {spinner:.bright_cyan} {msg}")
+            .into_diagnostic()?,
+    );
+    spinner.set_message("Checking for updates...".to_string());
+    spinner.enable_steady_tick(Duration::from_millis(80));
+
+    let version_spec = match args.target_version.as_deref() {
+        Some(target) => VersionSpec::parse(target)?,
+        None => VersionSpec::Latest,
+    };
+    let release = resolve_release(repo, &version_spec, &args.channel)?;
+    let target_version = release.tag_name.trim_start_matches('v');
+
+    spinner.finish_and_clear();
+
+    if !args.force && target_version == current_version {
+        println!(
+            "\n{}\n\n    {} {}\n\n{}\n",
+            "  ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan(),
+            "✓".bright_green().bold(),
+            "mq-update is already up-to-date!".bright_green().bold(),
+            "  ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan()
+        );
+        return Ok(());
+    }
+
+    let target_arch = get_target_arch();
+    let asset_name = format!("mq-update-{}", target_arch);
+
+    let asset = release
+        .assets
+        .iter()
+        .find(|a| a.name == asset_name)
+        .ok_or_else(|| {
+            miette::miette!("Could not find mq-update binary for architecture: {}", target_arch)
+        })?;
+
+    let verification = VerificationAssets {
+        sha256_url: release
+            .assets
+            .iter()
+            .find(|a| a.name == format!("{}.sha256", asset.name))
+            .map(|a| a.browser_download_url.clone()),
+        signature: release
+            .assets
+            .iter()
+            .find(|a| a.name == format!("{}.minisig", asset.name))
+            .map(|a| SignatureAsset::Minisign(a.browser_download_url.clone()))
+            .or_else(|| {
+                release
+                    .assets
+                    .iter()
+                    .find(|a| a.name == format!("{}.sig", asset.name))
+                    .map(|a| SignatureAsset::Raw(a.browser_download_url.clone()))
+            }),
+    };
+
+    confirm_replacement(args.force, false)?;
+
+    let client = github_client()?;
+    let buffer = download_binary(&client, &asset.browser_download_url)?;
+    verify_release_asset(&client, &buffer, &verification, args.no_verify, args.pubkey.as_deref())?;
+
+    replace_running_executable(&buffer, &current_exe)?;
+
+    println!(
+        "\n{}\n\n    {} {}\n    {} Version: {} {} {}\n\n{}\n",
+        "  ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan(),
+        "✓".bright_green().bold(),
+        "Successfully updated mq-update!".bright_green().bold(),
+        "│".bright_black(),
+        current_version.bright_cyan(),
+        "→".bright_white(),
+        target_version.bright_green().bold(),
+        "  ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan()
+    );
+
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    cleanup_self_update_leftovers();
+
+    let args = Args::parse();
+
+    print_logo();
+
+    match args.command {
+        Some(CliCommand::Rollback {
+            subcommand,
+            to,
+            list,
+        }) => return run_rollback(subcommand, to, list),
+        Some(CliCommand::SelfUpdate) => return self_update(&args),
+        None => {}
+    }
+
+    if args.all {
+        return run_all(&args);
+    }
+
+    let (binary_name, repo, display_name) = if let Some(ref sub) = args.subcommand {
+        (
+            format!("mq-{}", sub),
+            format!("harehare/mq-{}", sub),
+            format!("mq-{}", sub),
+        )
+    } else {
+        (
+            "mq".to_string(),
+            "harehare/mq".to_string(),
+            "mq".to_string(),
+        )
+    };
+
+    if args.current {
+        let is_new_install = get_binary_path(&binary_name)?.is_none();
+        let current_version = if is_new_install {
+            None
+        } else {
+            get_binary_version(&binary_name)?
+        };
+
+        if is_new_install {
+            println!(
+                "\n  📦 {}\n  {} {}\n  {}\n",
+                format!("{} is not installed", display_name)
+                    .bright_white()
+                    .bold(),
+                "├─".bright_black(),
+                "not found".bright_yellow().bold(),
+                "└─────────────────────────────".bright_black()
+            );
+        } else if let Some(ref ver) = current_version {
+            println!(
+                "\n  📦 {}\n  {} {}\n  {}\n",
+                format!("Current {} version", display_name)
+                    .bright_white()
+                    .bold(),
+                "├─".bright_black(),
+                ver.bright_green().bold(),
+                "└─────────────────────────────".bright_black()
+            );
+        } else {
+            println!(
+                "\n  📦 {}\n  {} {}\n  {}\n",
+                format!("Current {} version", display_name)
+                    .bright_white()
+                    .bold(),
+                "├─".bright_black(),
+                "unknown".bright_yellow().bold(),
+                "└─────────────────────────────".bright_black()
+            );
+        }
+        return Ok(());
+    }
+
+    update_one(&binary_name, &repo, &display_name, &args, false, None)?;
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(tag_name: &str, prerelease: bool) -> Release {
+        Release {
+            tag_name: tag_name.to_string(),
+            assets: Vec::new(),
+            prerelease,
+        }
+    }
+
+    #[test]
+    fn version_spec_parse_latest_is_case_insensitive() {
+        assert!(matches!(VersionSpec::parse("latest").unwrap(), VersionSpec::Latest));
+        assert!(matches!(VersionSpec::parse("LATEST").unwrap(), VersionSpec::Latest));
+        assert!(matches!(VersionSpec::parse("LaTeSt").unwrap(), VersionSpec::Latest));
+    }
+
+    #[test]
+    fn version_spec_parse_exact_version_with_and_without_v_prefix() {
+        match VersionSpec::parse("v1.2.3").unwrap() {
+            VersionSpec::Exact(version) => assert_eq!(version, semver::Version::new(1, 2, 3)),
+            _ => panic!("expected VersionSpec::Exact"),
+        }
+        match VersionSpec::parse("1.2.3").unwrap() {
+            VersionSpec::Exact(version) => assert_eq!(version, semver::Version::new(1, 2, 3)),
+            _ => panic!("expected VersionSpec::Exact"),
+        }
+    }
+
+    #[test]
+    fn version_spec_parse_requirement() {
+        match VersionSpec::parse("^1.2").unwrap() {
+            VersionSpec::Req(req) => {
+                assert!(req.matches(&semver::Version::new(1, 3, 0)));
+                assert!(!req.matches(&semver::Version::new(2, 0, 0)));
+            }
+            _ => panic!("expected VersionSpec::Req"),
+        }
+    }
+
+    #[test]
+    fn version_spec_parse_rejects_garbage() {
+        assert!(VersionSpec::parse("not a version").is_err());
+    }
+
+    #[test]
+    fn verify_checksum_accepts_matching_digest() {
+        let buffer = b"hello world";
+        let mut hasher = Sha256::new();
+        hasher.update(buffer);
+        let expected = hex::encode(hasher.finalize());
+        assert!(verify_checksum(buffer, &expected).is_ok());
+    }
+
+    #[test]
+    fn verify_checksum_rejects_mismatched_digest() {
+        let buffer = b"hello world";
+        let wrong = "0".repeat(64);
+        assert!(verify_checksum(buffer, &wrong).is_err());
+    }
+
+    #[test]
+    fn verify_checksum_strips_trailing_filename() {
+        let buffer = b"hello world";
+        let mut hasher = Sha256::new();
+        hasher.update(buffer);
+        let digest = hex::encode(hasher.finalize());
+        let line = format!("{}  mq-check-x86_64-unknown-linux-gnu", digest);
+        assert!(verify_checksum(buffer, &line).is_ok());
+    }
+
+    #[test]
+    fn verify_checksum_is_case_insensitive() {
+        let buffer = b"hello world";
+        let mut hasher = Sha256::new();
+        hasher.update(buffer);
+        let digest = hex::encode(hasher.finalize()).to_uppercase();
+        assert!(verify_checksum(buffer, &digest).is_ok());
+    }
+
+    #[test]
+    fn verify_signature_rejects_wrong_length_bytes() {
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+        let public_key = signing_key.verifying_key();
+        let too_short = vec![0u8; 63];
+        assert!(verify_signature(b"data", &too_short, &public_key).is_err());
+        let too_long = vec![0u8; 65];
+        assert!(verify_signature(b"data", &too_long, &public_key).is_err());
+    }
+
+    #[test]
+    fn sanitize_version_component_rejects_empty() {
+        assert!(sanitize_version_component("").is_err());
+    }
+
+    #[test]
+    fn sanitize_version_component_rejects_path_separators() {
+        assert!(sanitize_version_component("v1/../../etc").is_err());
+        assert!(sanitize_version_component("v1/evil").is_err());
+        assert!(sanitize_version_component("v1\\evil").is_err());
+    }
+
+    #[test]
+    fn sanitize_version_component_rejects_dot_dot() {
+        assert!(sanitize_version_component("..").is_err());
+        assert!(sanitize_version_component("v1.0.0..").is_err());
+    }
+
+    #[test]
+    fn sanitize_version_component_accepts_normal_version() {
+        assert_eq!(sanitize_version_component("v1.2.3").unwrap(), "v1.2.3");
+    }
+
+    #[test]
+    fn parse_next_link_finds_rel_next() {
+        let header = r#"<https://api.github.com/repos/x/y/releases?page=2>; rel="next", <https://api.github.com/repos/x/y/releases?page=5>; rel="last""#;
+        assert_eq!(
+            parse_next_link(header).as_deref(),
+            Some("https://api.github.com/repos/x/y/releases?page=2")
+        );
+    }
+
+    #[test]
+    fn parse_next_link_absent_without_rel_next() {
+        let header = r#"<https://api.github.com/repos/x/y/releases?page=1>; rel="prev", <https://api.github.com/repos/x/y/releases?page=5>; rel="last""#;
+        assert_eq!(parse_next_link(header), None);
+    }
+
+    #[test]
+    fn parse_next_link_malformed_header() {
+        assert_eq!(parse_next_link("not a link header"), None);
+    }
+
+    #[test]
+    fn matches_channel_prerelease_only_matches_prerelease_channel() {
+        let pre = release("v1.0.0-beta.1", true);
+        assert!(!matches_channel(&pre, &Channel::Stable));
+        assert!(matches_channel(&pre, &Channel::Prerelease));
+    }
+
+    #[test]
+    fn matches_channel_stable_matches_both_channels() {
+        let stable = release("v1.0.0", false);
+        assert!(matches_channel(&stable, &Channel::Stable));
+        assert!(matches_channel(&stable, &Channel::Prerelease));
+    }
+
+    #[test]
+    fn verify_minisign_accepts_self_signed_round_trip() {
+        use ed25519_dalek::Signer;
+
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&[9u8; 32]);
+        let public_key = signing_key.verifying_key();
+        let buffer = b"some release binary bytes";
+
+        let mut sig_blob = [0u8; 74];
+        sig_blob[0] = b'E';
+        sig_blob[1] = b'd';
+        let file_sig = signing_key.sign(buffer);
+        sig_blob[10..74].copy_from_slice(&file_sig.to_bytes());
+
+        let trusted_comment = "timestamp:1234567890";
+        let mut global_message = sig_blob[10..74].to_vec();
+        global_message.extend_from_slice(trusted_comment.as_bytes());
+        let global_sig = signing_key.sign(&global_message);
+
+        let minisig_text = format!(
+            "untrusted comment: test\n{}\ntrusted comment:{}\n{}\n",
+            base64::engine::general_purpose::STANDARD.encode(sig_blob),
+            trusted_comment,
+            base64::engine::general_purpose::STANDARD.encode(global_sig.to_bytes()),
+        );
+
+        assert!(verify_minisign(buffer, &minisig_text, &public_key).is_ok());
+    }
+
+    #[test]
+    fn verify_minisign_rejects_tampered_trusted_comment() {
+        use ed25519_dalek::Signer;
+
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&[9u8; 32]);
+        let public_key = signing_key.verifying_key();
+        let buffer = b"some release binary bytes";
+
+        let mut sig_blob = [0u8; 74];
+        sig_blob[0] = b'E';
+        sig_blob[1] = b'd';
+        let file_sig = signing_key.sign(buffer);
+        sig_blob[10..74].copy_from_slice(&file_sig.to_bytes());
+
+        let mut global_message = sig_blob[10..74].to_vec();
+        global_message.extend_from_slice(b"timestamp:1234567890");
+        let global_sig = signing_key.sign(&global_message);
+
+        let minisig_text = format!(
+            "untrusted comment: test\n{}\ntrusted comment:timestamp:9999999999\n{}\n",
+            base64::engine::general_purpose::STANDARD.encode(sig_blob),
+            base64::engine::general_purpose::STANDARD.encode(global_sig.to_bytes()),
+        );
+
+        assert!(verify_minisign(buffer, &minisig_text, &public_key).is_err());
+    }
+}